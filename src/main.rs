@@ -22,30 +22,94 @@ struct App {
     #[clap(long, short = 't', global = true)]
     accept_pings: Option<usize>,
 
+    /// Read timeout applied to every socket of the `tcp` and `udp` subcommands, in milliseconds
+    #[clap(long, global = true)]
+    read_timeout_ms: Option<u64>,
+
+    /// Write timeout applied to every socket of the `tcp` and `udp` subcommands, in milliseconds
+    #[clap(long, global = true)]
+    write_timeout_ms: Option<u64>,
+
+    /// TTL applied to every socket of the `tcp` and `udp` subcommands
+    #[clap(long, global = true)]
+    ttl: Option<u32>,
+
+    /// Allow binding/connecting to an address, in `<addr-or-cidr>:<port>` form (e.g.
+    /// `127.0.0.1:5000` or `10.0.0.0/8:1000-2000`). Repeatable. If no `--allow` is given, every
+    /// address is allowed, preserving the tool's default loopback-only behavior.
+    #[clap(long = "allow", global = true)]
+    allow: Vec<String>,
+
     #[clap(subcommand)]
     cmd: Command,
 }
 
 impl App {
+    fn socket_timeouts(&self) -> SocketTimeouts {
+        SocketTimeouts {
+            read_timeout: self.read_timeout_ms.map(Duration::from_millis),
+            write_timeout: self.write_timeout_ms.map(Duration::from_millis),
+            ttl: self.ttl,
+        }
+    }
+
     fn exec(self) -> Result<()> {
         if self.reset_sigpipe {
             eprintln!("Resetting SIGPIPE handler");
             sigpipe::reset();
         }
 
+        let timeouts = self.socket_timeouts();
+        let pool = Pool::parse(&self.allow).context("failed to parse --allow entries")?;
+
         match self.cmd {
-            Command::Tcp => {
-                let addr = spawn_tcp_ping_thread(self.accept_pings)
-                    .context("failed to spawn TCP ping thread")?;
-                eprintln!("TCP ping listening on {}", addr);
-                ping_tcp(&addr).context("failed to ping TCP")?;
+            Command::Tcp { shutdown, target } => {
+                if shutdown.is_some() && self.accept_pings.is_none() {
+                    anyhow::bail!(
+                        "--shutdown requires --accept-pings, otherwise the server-side loop \
+                         never reaches the point that triggers the shutdown"
+                    );
+                }
+                if shutdown.is_some() && target.is_some() {
+                    anyhow::bail!(
+                        "--shutdown only applies to the locally-spawned echo server and has no \
+                         effect together with --target"
+                    );
+                }
+
+                let addr = match target {
+                    Some(addr) => {
+                        eprintln!("TCP ping targeting {}", addr);
+                        addr
+                    }
+                    None => {
+                        let addr =
+                            spawn_tcp_ping_thread(self.accept_pings, timeouts, &pool, shutdown)
+                                .context("failed to spawn TCP ping thread")?;
+                        eprintln!("TCP ping listening on {}", addr);
+                        addr
+                    }
+                };
+                ping_tcp(&addr, timeouts, &pool).context("failed to ping TCP")?;
             }
-            Command::Udp => {
-                let addr = spawn_udp_ping_thread(self.accept_pings)
-                    .context("failed to spawn UDP ping thread")?;
-                eprintln!("UDP ping listening on {}", addr);
+            Command::Udp { connected, target } => {
+                let addr = match target {
+                    Some(addr) => {
+                        eprintln!("UDP ping targeting {}", addr);
+                        addr
+                    }
+                    None => {
+                        let addr = spawn_udp_ping_thread(self.accept_pings, timeouts, &pool)
+                            .context("failed to spawn UDP ping thread")?;
+                        eprintln!("UDP ping listening on {}", addr);
+                        addr
+                    }
+                };
 
-                ping_udp(&addr).context("failed to ping UDP")?;
+                ping_udp(&addr, connected, timeouts, &pool).context("failed to ping UDP")?;
+            }
+            Command::Verify { tcp, udp, retries } => {
+                verify_ports(&tcp, &udp, retries, &pool)?;
             }
         }
 
@@ -53,14 +117,229 @@ impl App {
     }
 }
 
+/// Read/write timeouts and TTL shared by every socket the `tcp` and `udp` subcommands open, so
+/// that a silent peer or a lost datagram can't wedge the process indefinitely. `verify` does not
+/// use them.
+#[derive(Debug, Clone, Copy, Default)]
+struct SocketTimeouts {
+    read_timeout: Option<Duration>,
+    write_timeout: Option<Duration>,
+    ttl: Option<u32>,
+}
+
+/// A capability-style allowlist of addresses this tool may bind or connect to, modeled on
+/// cap-std's `Pool`. An empty pool allows every address, so the tool behaves exactly as before
+/// unless the user opts in with `--allow`.
+#[derive(Debug, Clone, Default)]
+struct Pool {
+    entries: Vec<(IpRange, PortRange)>,
+}
+
+impl Pool {
+    fn parse(allow: &[String]) -> Result<Self> {
+        let mut entries = Vec::with_capacity(allow.len());
+        for entry in allow {
+            let (addr_part, port_part) = entry.rsplit_once(':').with_context(|| {
+                format!(
+                    "invalid --allow entry {:?}, expected <addr-or-cidr>:<port>",
+                    entry
+                )
+            })?;
+            let ip_range = IpRange::parse(addr_part)
+                .with_context(|| format!("invalid address in --allow entry {:?}", entry))?;
+            let port_range = PortRange::parse(port_part)
+                .with_context(|| format!("invalid port in --allow entry {:?}", entry))?;
+            entries.push((ip_range, port_range));
+        }
+        Ok(Pool { entries })
+    }
+
+    fn check_addr(&self, addr: &SocketAddr) -> Result<()> {
+        if self.entries.is_empty() {
+            return Ok(());
+        }
+
+        let allowed = self.entries.iter().any(|(ip_range, port_range)| {
+            ip_range.contains(&addr.ip()) && port_range.contains(addr.port())
+        });
+
+        if allowed {
+            Ok(())
+        } else {
+            anyhow::bail!("address {} not in pool", addr)
+        }
+    }
+}
+
+/// An IP network expressed as `<addr>` or `<addr>/<prefix-len>`.
+#[derive(Debug, Clone, Copy)]
+struct IpRange {
+    network: std::net::IpAddr,
+    prefix_len: u8,
+}
+
+impl IpRange {
+    fn parse(s: &str) -> Result<Self> {
+        let (addr_str, prefix_len) = match s.split_once('/') {
+            Some((addr, len)) => (
+                addr,
+                Some(len.parse::<u8>().context("invalid CIDR prefix length")?),
+            ),
+            None => (s, None),
+        };
+
+        let network: std::net::IpAddr = addr_str.parse().context("invalid IP address")?;
+        let max_len = if network.is_ipv4() { 32 } else { 128 };
+        let prefix_len = prefix_len.unwrap_or(max_len);
+        if prefix_len > max_len {
+            anyhow::bail!("prefix length {} exceeds {} bits", prefix_len, max_len);
+        }
+
+        Ok(IpRange {
+            network,
+            prefix_len,
+        })
+    }
+
+    fn contains(&self, ip: &std::net::IpAddr) -> bool {
+        use std::net::IpAddr;
+
+        match (self.network, ip) {
+            (IpAddr::V4(network), IpAddr::V4(ip)) => {
+                let mask = mask_of_len(self.prefix_len, 32) as u32;
+                (u32::from(network) & mask) == (u32::from(*ip) & mask)
+            }
+            (IpAddr::V6(network), IpAddr::V6(ip)) => {
+                let mask = mask_of_len(self.prefix_len, 128);
+                (u128::from(network) & mask) == (u128::from(*ip) & mask)
+            }
+            _ => false,
+        }
+    }
+}
+
+fn mask_of_len(prefix_len: u8, width: u32) -> u128 {
+    if prefix_len == 0 {
+        0
+    } else {
+        !0u128 << (width - u32::from(prefix_len))
+    }
+}
+
+/// An inclusive port range expressed as `<port>` or `<start>-<end>`.
+#[derive(Debug, Clone, Copy)]
+struct PortRange {
+    start: u16,
+    end: u16,
+}
+
+impl PortRange {
+    fn parse(s: &str) -> Result<Self> {
+        match s.split_once('-') {
+            Some((start, end)) => Ok(PortRange {
+                start: start.parse().context("invalid port")?,
+                end: end.parse().context("invalid port")?,
+            }),
+            None => {
+                let port: u16 = s.parse().context("invalid port")?;
+                Ok(PortRange {
+                    start: port,
+                    end: port,
+                })
+            }
+        }
+    }
+
+    fn contains(&self, port: u16) -> bool {
+        (self.start..=self.end).contains(&port)
+    }
+}
+
+/// Which half (or both) of a TCP connection the server should explicitly shut down after
+/// `accept_pings` pings, instead of just dropping the stream and racing the client.
+#[derive(Debug, Clone, Copy, clap::ValueEnum)]
+enum ShutdownMode {
+    Read,
+    Write,
+    Both,
+}
+
+impl From<ShutdownMode> for std::net::Shutdown {
+    fn from(mode: ShutdownMode) -> Self {
+        match mode {
+            ShutdownMode::Read => std::net::Shutdown::Read,
+            ShutdownMode::Write => std::net::Shutdown::Write,
+            ShutdownMode::Both => std::net::Shutdown::Both,
+        }
+    }
+}
+
 #[derive(Debug, Subcommand)]
 enum Command {
-    Tcp,
-    Udp,
+    Tcp {
+        /// After `accept_pings` pings, explicitly shut down this half (or both halves) of the
+        /// connection instead of just dropping the stream, so the client hits the EPIPE/SIGPIPE
+        /// condition at a well-defined moment rather than racing a drop.
+        ///
+        /// Requires `--accept-pings`: without a fixed ping count the server-side loop never
+        /// reaches the end that triggers the shutdown, so the connection would just run forever.
+        #[clap(long, value_enum)]
+        shutdown: Option<ShutdownMode>,
+
+        /// Connect to this address instead of spawning a local loopback echo server.
+        ///
+        /// Use this to point the tool at a remote echo server; combine with `--allow` to bound
+        /// which hosts it's permitted to reach.
+        #[clap(long)]
+        target: Option<SocketAddr>,
+    },
+    Udp {
+        /// Connect the UDP socket to the peer instead of using send_to/recv_from.
+        ///
+        /// On Linux, a connected UDP socket turns an asynchronous ICMP "port
+        /// unreachable" reply into an ECONNREFUSED error on the *next* send or
+        /// recv, which lets us exercise the same broken-pipe-style failure path
+        /// that the TCP side hits, without relying on a signal.
+        #[clap(long)]
+        connected: bool,
+
+        /// Send to this address instead of spawning a local loopback echo server.
+        ///
+        /// Use this to point the tool at a remote echo server; combine with `--allow` to bound
+        /// which hosts it's permitted to reach.
+        #[clap(long)]
+        target: Option<SocketAddr>,
+    },
+    /// Probe a list of TCP/UDP ports on 127.0.0.1 and report which are reachable.
+    Verify {
+        /// TCP ports to probe
+        #[clap(long)]
+        tcp: Vec<u16>,
+
+        /// UDP ports to probe
+        #[clap(long)]
+        udp: Vec<u16>,
+
+        /// Number of times to retry a UDP probe before declaring the port unreachable
+        ///
+        /// UDP is lossy, so a single dropped probe shouldn't be enough to condemn a port.
+        #[clap(long, default_value = "2")]
+        retries: usize,
+    },
 }
 
-fn spawn_tcp_ping_thread(accept_pings: Option<usize>) -> Result<SocketAddr> {
-    let listener = TcpListener::bind("127.0.0.1:0").context("failed to bind")?;
+fn spawn_tcp_ping_thread(
+    accept_pings: Option<usize>,
+    timeouts: SocketTimeouts,
+    pool: &Pool,
+    shutdown: Option<ShutdownMode>,
+) -> Result<SocketAddr> {
+    let bind_addr: SocketAddr = "127.0.0.1:0".parse().expect("valid address");
+    pool.check_addr(&bind_addr)?;
+    let listener = TcpListener::bind(bind_addr).context("failed to bind")?;
+    if let Some(ttl) = timeouts.ttl {
+        listener.set_ttl(ttl).context("failed to set TTL")?;
+    }
     let addr = listener
         .local_addr()
         .context("failed to get local address")?;
@@ -69,18 +348,29 @@ fn spawn_tcp_ping_thread(accept_pings: Option<usize>) -> Result<SocketAddr> {
         for stream in listener.incoming() {
             match stream {
                 Ok(mut stream) => {
+                    if apply_timeouts(&stream, timeouts).is_err() {
+                        break;
+                    }
+
+                    let pings_to_accept = accept_pings.unwrap_or(usize::MAX);
+                    let mut pings_handled = 0;
                     let mut buf = [0; 1024];
-                    for _ in 0..accept_pings.unwrap_or(usize::MAX) {
+                    for _ in 0..pings_to_accept {
                         match stream.read(&mut buf) {
                             Ok(0) => break,
                             Ok(n) => {
                                 if stream.write_all(&buf[..n]).is_err() {
                                     break;
                                 }
+                                pings_handled += 1;
                             }
                             Err(_) => break,
                         }
                     }
+
+                    if let Some(mode) = shutdown.filter(|_| pings_handled == pings_to_accept) {
+                        let _ = stream.shutdown(mode.into());
+                    }
                 }
                 Err(_) => break,
             }
@@ -90,31 +380,46 @@ fn spawn_tcp_ping_thread(accept_pings: Option<usize>) -> Result<SocketAddr> {
     Ok(addr)
 }
 
-fn ping_tcp(addr: &SocketAddr) -> Result<()> {
+fn ping_tcp(addr: &SocketAddr, timeouts: SocketTimeouts, pool: &Pool) -> Result<()> {
+    pool.check_addr(addr)?;
     let mut stream = TcpStream::connect(addr).context("failed to connect")?;
+    apply_timeouts(&stream, timeouts)?;
 
     for n in 0.. {
         eprintln!("ping {}", n);
-        stream.write_all(b"ping")?;
+        stream
+            .write_all(b"ping")
+            .map_err(classify_io_error("write"))?;
 
         let mut buf = [0; 1024];
-        stream.read(&mut buf)?;
+        stream.read(&mut buf).map_err(classify_io_error("read"))?;
         thread::sleep(Duration::from_secs(1));
     }
     Ok(())
 }
 
-fn spawn_udp_ping_thread(accept_pings: Option<usize>) -> Result<SocketAddr> {
-    let listener = UdpSocket::bind("127.0.0.1:0").context("failed to bind")?;
+fn spawn_udp_ping_thread(
+    accept_pings: Option<usize>,
+    timeouts: SocketTimeouts,
+    pool: &Pool,
+) -> Result<SocketAddr> {
+    let bind_addr: SocketAddr = "127.0.0.1:0".parse().expect("valid address");
+    pool.check_addr(&bind_addr)?;
+    let listener = UdpSocket::bind(bind_addr).context("failed to bind")?;
+    apply_timeouts(&listener, timeouts)?;
     let addr = listener
         .local_addr()
         .context("failed to get local address")?;
+    let pool = pool.clone();
 
     thread::spawn(move || {
         let mut buf = [0; 1024];
         for _ in 0..accept_pings.unwrap_or(usize::MAX) {
             match listener.recv_from(&mut buf) {
                 Ok((n, addr)) => {
+                    if pool.check_addr(&addr).is_err() {
+                        break;
+                    }
                     if listener.send_to(&buf[..n], addr).is_err() {
                         break;
                     }
@@ -127,19 +432,291 @@ fn spawn_udp_ping_thread(accept_pings: Option<usize>) -> Result<SocketAddr> {
     Ok(addr)
 }
 
-fn ping_udp(addr: &SocketAddr) -> Result<()> {
+fn ping_udp(
+    addr: &SocketAddr,
+    connected: bool,
+    timeouts: SocketTimeouts,
+    pool: &Pool,
+) -> Result<()> {
+    pool.check_addr(addr)?;
+
     // UDP is connectionless, so all we're doing here is creating a socket at some arbitrary
     // address.
-    let socket = UdpSocket::bind("127.0.0.1:0").context("failed to connect")?;
+    // A socket bound to loopback can only reach loopback peers, so bind to the unspecified
+    // address of the peer's family for any other `--target`.
+    let bind_addr: SocketAddr = match addr {
+        SocketAddr::V4(v4) if v4.ip().is_loopback() => "127.0.0.1:0",
+        SocketAddr::V4(_) => "0.0.0.0:0",
+        SocketAddr::V6(_) => "[::]:0",
+    }
+    .parse()
+    .expect("valid address");
+    pool.check_addr(&bind_addr)?;
+    let socket = UdpSocket::bind(bind_addr).context("failed to bind")?;
+    apply_timeouts(&socket, timeouts)?;
+
+    if connected {
+        socket.connect(addr).context("failed to connect")?;
+    }
 
     for n in 0.. {
         eprintln!("ping {}", n);
-        socket.send_to(b"ping", addr)?;
 
         let mut buf = [0; 1024];
-        socket.recv_from(&mut buf)?;
+        if connected {
+            socket.send(b"ping").map_err(classify_io_error("send"))?;
+            socket.recv(&mut buf).map_err(classify_io_error("recv"))?;
+        } else {
+            socket
+                .send_to(b"ping", addr)
+                .map_err(classify_io_error("send_to"))?;
+            socket
+                .recv_from(&mut buf)
+                .map_err(classify_io_error("recv_from"))?;
+        }
         thread::sleep(Duration::from_secs(1));
     }
 
     Ok(())
 }
+
+/// Apply the read timeout, write timeout, and TTL in `timeouts` to a TCP or UDP socket.
+fn apply_timeouts(socket: &impl SetSocketTimeouts, timeouts: SocketTimeouts) -> Result<()> {
+    socket
+        .set_read_timeout(timeouts.read_timeout)
+        .context("failed to set read timeout")?;
+    socket
+        .set_write_timeout(timeouts.write_timeout)
+        .context("failed to set write timeout")?;
+    if let Some(ttl) = timeouts.ttl {
+        socket.set_ttl(ttl).context("failed to set TTL")?;
+    }
+    Ok(())
+}
+
+/// The subset of `TcpStream`/`UdpSocket` methods needed to apply `SocketTimeouts`.
+trait SetSocketTimeouts {
+    fn set_read_timeout(&self, dur: Option<Duration>) -> std::io::Result<()>;
+    fn set_write_timeout(&self, dur: Option<Duration>) -> std::io::Result<()>;
+    fn set_ttl(&self, ttl: u32) -> std::io::Result<()>;
+}
+
+impl SetSocketTimeouts for TcpStream {
+    fn set_read_timeout(&self, dur: Option<Duration>) -> std::io::Result<()> {
+        TcpStream::set_read_timeout(self, dur)
+    }
+
+    fn set_write_timeout(&self, dur: Option<Duration>) -> std::io::Result<()> {
+        TcpStream::set_write_timeout(self, dur)
+    }
+
+    fn set_ttl(&self, ttl: u32) -> std::io::Result<()> {
+        TcpStream::set_ttl(self, ttl)
+    }
+}
+
+impl SetSocketTimeouts for UdpSocket {
+    fn set_read_timeout(&self, dur: Option<Duration>) -> std::io::Result<()> {
+        UdpSocket::set_read_timeout(self, dur)
+    }
+
+    fn set_write_timeout(&self, dur: Option<Duration>) -> std::io::Result<()> {
+        UdpSocket::set_write_timeout(self, dur)
+    }
+
+    fn set_ttl(&self, ttl: u32) -> std::io::Result<()> {
+        UdpSocket::set_ttl(self, ttl)
+    }
+}
+
+/// Turn a `WouldBlock`/`TimedOut` I/O error into a distinct, clearly-labeled error so that a
+/// timeout is never mistaken for a connection reset during SIGPIPE/EPIPE testing.
+fn classify_io_error(op: &'static str) -> impl FnOnce(std::io::Error) -> anyhow::Error {
+    move |err| {
+        if matches!(
+            err.kind(),
+            std::io::ErrorKind::WouldBlock | std::io::ErrorKind::TimedOut
+        ) {
+            anyhow::anyhow!("{} timed out: {}", op, err)
+        } else {
+            anyhow::Error::new(err).context(format!("{} failed", op))
+        }
+    }
+}
+
+/// Probe every requested TCP and UDP port on 127.0.0.1, print a per-port reachability summary,
+/// and return an error if any port was unreachable.
+fn verify_ports(tcp_ports: &[u16], udp_ports: &[u16], retries: usize, pool: &Pool) -> Result<()> {
+    let mut unreachable = Vec::new();
+
+    for &port in tcp_ports {
+        let reachable = verify_tcp_port(port, pool);
+        println!(
+            "tcp/{}: {}",
+            port,
+            if reachable {
+                "reachable"
+            } else {
+                "unreachable"
+            }
+        );
+        if !reachable {
+            unreachable.push(format!("tcp/{}", port));
+        }
+    }
+
+    for &port in udp_ports {
+        let reachable = verify_udp_port(port, retries, pool);
+        println!(
+            "udp/{}: {}",
+            port,
+            if reachable {
+                "reachable"
+            } else {
+                "unreachable"
+            }
+        );
+        if !reachable {
+            unreachable.push(format!("udp/{}", port));
+        }
+    }
+
+    if unreachable.is_empty() {
+        Ok(())
+    } else {
+        anyhow::bail!("unreachable ports: {}", unreachable.join(", "));
+    }
+}
+
+fn verify_tcp_port(port: u16, pool: &Pool) -> bool {
+    let addr = SocketAddr::from(([127, 0, 0, 1], port));
+    if pool.check_addr(&addr).is_err() {
+        return false;
+    }
+
+    let mut stream = match TcpStream::connect_timeout(&addr, Duration::from_secs(1)) {
+        Ok(stream) => stream,
+        Err(_) => return false,
+    };
+
+    if stream.write_all(b"ping").is_err() {
+        return false;
+    }
+    let mut buf = [0; 1024];
+    stream.read(&mut buf).is_ok()
+}
+
+fn verify_udp_port(port: u16, retries: usize, pool: &Pool) -> bool {
+    let addr = SocketAddr::from(([127, 0, 0, 1], port));
+    if pool.check_addr(&addr).is_err() {
+        return false;
+    }
+
+    let bind_addr: SocketAddr = "127.0.0.1:0".parse().expect("valid address");
+    if pool.check_addr(&bind_addr).is_err() {
+        return false;
+    }
+
+    let socket = match UdpSocket::bind(bind_addr) {
+        Ok(socket) => socket,
+        Err(_) => return false,
+    };
+    if socket
+        .set_read_timeout(Some(Duration::from_millis(500)))
+        .is_err()
+    {
+        return false;
+    }
+
+    for attempt in 0..=retries {
+        if socket.send_to(b"ping", addr).is_err() {
+            continue;
+        }
+
+        let mut buf = [0; 1024];
+        match socket.recv_from(&mut buf) {
+            Ok(_) => return true,
+            Err(_) if attempt < retries => {
+                eprintln!("udp/{}: probe {} timed out, retrying", port, attempt);
+            }
+            Err(_) => {
+                eprintln!("udp/{}: probe {} timed out", port, attempt);
+            }
+        }
+    }
+
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ip_range_zero_prefix_matches_whole_family() {
+        let range = IpRange::parse("0.0.0.0/0").unwrap();
+        assert!(range.contains(&"1.2.3.4".parse().unwrap()));
+        assert!(range.contains(&"255.255.255.255".parse().unwrap()));
+
+        let range = IpRange::parse("::/0").unwrap();
+        assert!(range.contains(&"::1".parse().unwrap()));
+        assert!(range.contains(&"2001:db8::1".parse().unwrap()));
+    }
+
+    #[test]
+    fn ip_range_max_prefix_matches_only_exact_address() {
+        let range = IpRange::parse("192.168.1.1/32").unwrap();
+        assert!(range.contains(&"192.168.1.1".parse().unwrap()));
+        assert!(!range.contains(&"192.168.1.2".parse().unwrap()));
+
+        let range = IpRange::parse("::1/128").unwrap();
+        assert!(range.contains(&"::1".parse().unwrap()));
+        assert!(!range.contains(&"::2".parse().unwrap()));
+    }
+
+    #[test]
+    fn ip_range_subnet_matches_within_bounds() {
+        let range = IpRange::parse("10.0.0.0/24").unwrap();
+        assert!(range.contains(&"10.0.0.1".parse().unwrap()));
+        assert!(range.contains(&"10.0.0.255".parse().unwrap()));
+        assert!(!range.contains(&"10.0.1.0".parse().unwrap()));
+
+        let range = IpRange::parse("2001:db8::/32").unwrap();
+        assert!(range.contains(&"2001:db8::1".parse().unwrap()));
+        assert!(!range.contains(&"2001:db9::1".parse().unwrap()));
+    }
+
+    #[test]
+    fn ip_range_rejects_mismatched_address_family() {
+        let v4_range = IpRange::parse("0.0.0.0/0").unwrap();
+        assert!(!v4_range.contains(&"::1".parse().unwrap()));
+
+        let v6_range = IpRange::parse("::/0").unwrap();
+        assert!(!v6_range.contains(&"127.0.0.1".parse().unwrap()));
+    }
+
+    #[test]
+    fn port_range_single_port() {
+        let range = PortRange::parse("8080").unwrap();
+        assert!(range.contains(8080));
+        assert!(!range.contains(8081));
+    }
+
+    #[test]
+    fn port_range_inclusive_bounds() {
+        let range = PortRange::parse("1000-2000").unwrap();
+        assert!(range.contains(1000));
+        assert!(range.contains(2000));
+        assert!(range.contains(1500));
+        assert!(!range.contains(999));
+        assert!(!range.contains(2001));
+    }
+
+    #[test]
+    fn port_range_reversed_bounds_contains_nothing() {
+        let range = PortRange::parse("2000-1000").unwrap();
+        assert!(!range.contains(1000));
+        assert!(!range.contains(1500));
+        assert!(!range.contains(2000));
+    }
+}